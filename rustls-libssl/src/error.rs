@@ -1,8 +1,14 @@
-use core::ffi::{c_int, c_long};
+use core::ffi::{c_int, c_long, c_ulong};
+use core::panic::Location;
 use core::ptr;
+use std::cell::Cell;
 use std::ffi::{CStr, CString};
+use std::sync::Once;
 
-use openssl_sys::{ERR_new, ERR_set_error, ERR_RFLAGS_OFFSET, ERR_RFLAG_FATAL};
+use openssl_sys::{
+    ERR_load_strings, ERR_new, ERR_set_debug, ERR_set_error, ERR_RFLAGS_OFFSET, ERR_RFLAG_FATAL,
+    ERR_STRING_DATA,
+};
 
 // See openssl/err.h for the source of these magic numbers.
 
@@ -26,69 +32,225 @@ enum Reason {
     UnableToGetWriteLock = (ERR_RFLAG_FATAL as i32) | ERR_RFLAG_COMMON | 272,
     OperationFailed = (ERR_RFLAG_FATAL as i32) | ERR_RFLAG_COMMON | 263,
     Unsupported = ERR_RFLAG_COMMON | 268,
+
+    // The following are the standard `SSL_R_*` reason codes from
+    // `openssl/sslerr.h`, reported as-is (without the shim's own
+    // `ERR_RFLAG_*` bits) so that `ERR_GET_REASON` matches what callers
+    // of real OpenSSL see for the same failure.
+    NoSharedCipher = 193,
+    CertificateVerifyFailed = 134,
+    UnsupportedProtocol = 258,
+    // No current `rustls::Error` variant maps cleanly to this; kept for
+    // when one does, rather than re-deriving the SSL_R_* value later.
+    #[allow(dead_code)]
+    WrongVersionNumber = 267,
+    // `SSL_R_TLSV1_ALERT_*` is `1000 + <TLS alert wire code>` (see
+    // `openssl/sslerr.h`); cross-checked against the wire codes in
+    // `TLS_ALERT_*` below by the `const _: () = ...` assertions further
+    // down, so the two can't silently drift apart.
+    TlsV1AlertAccessDenied = 1049,
+    TlsV1AlertUnknownCa = 1048,
+    TlsV1AlertDecodeError = 1050,
+    TlsV1AlertDecryptError = 1051,
+    TlsV1AlertExportRestriction = 1060,
+    TlsV1AlertProtocolVersion = 1070,
+    TlsV1AlertInsufficientSecurity = 1071,
+    TlsV1AlertInternalError = 1080,
+    TlsV1AlertUserCancelled = 1090,
+    TlsV1AlertNoRenegotiation = 1100,
 }
 
+// TLS alert wire codes (see the IANA TLS Alert Registry / RFC 8446 §6).
+const TLS_ALERT_UNKNOWN_CA: i32 = 48;
+const TLS_ALERT_ACCESS_DENIED: i32 = 49;
+const TLS_ALERT_DECODE_ERROR: i32 = 50;
+const TLS_ALERT_DECRYPT_ERROR: i32 = 51;
+const TLS_ALERT_EXPORT_RESTRICTION: i32 = 60;
+const TLS_ALERT_PROTOCOL_VERSION: i32 = 70;
+const TLS_ALERT_INSUFFICIENT_SECURITY: i32 = 71;
+const TLS_ALERT_INTERNAL_ERROR: i32 = 80;
+const TLS_ALERT_USER_CANCELED: i32 = 90;
+const TLS_ALERT_NO_RENEGOTIATION: i32 = 100;
+
+const SSL_R_ALERT_OFFSET: i32 = 1000;
+
+const _: () = {
+    assert!(Reason::TlsV1AlertUnknownCa as i32 == SSL_R_ALERT_OFFSET + TLS_ALERT_UNKNOWN_CA);
+    assert!(Reason::TlsV1AlertAccessDenied as i32 == SSL_R_ALERT_OFFSET + TLS_ALERT_ACCESS_DENIED);
+    assert!(Reason::TlsV1AlertDecodeError as i32 == SSL_R_ALERT_OFFSET + TLS_ALERT_DECODE_ERROR);
+    assert!(Reason::TlsV1AlertDecryptError as i32 == SSL_R_ALERT_OFFSET + TLS_ALERT_DECRYPT_ERROR);
+    assert!(
+        Reason::TlsV1AlertExportRestriction as i32
+            == SSL_R_ALERT_OFFSET + TLS_ALERT_EXPORT_RESTRICTION
+    );
+    assert!(
+        Reason::TlsV1AlertProtocolVersion as i32 == SSL_R_ALERT_OFFSET + TLS_ALERT_PROTOCOL_VERSION
+    );
+    assert!(
+        Reason::TlsV1AlertInsufficientSecurity as i32
+            == SSL_R_ALERT_OFFSET + TLS_ALERT_INSUFFICIENT_SECURITY
+    );
+    assert!(
+        Reason::TlsV1AlertInternalError as i32 == SSL_R_ALERT_OFFSET + TLS_ALERT_INTERNAL_ERROR
+    );
+    assert!(Reason::TlsV1AlertUserCancelled as i32 == SSL_R_ALERT_OFFSET + TLS_ALERT_USER_CANCELED);
+    assert!(
+        Reason::TlsV1AlertNoRenegotiation as i32 == SSL_R_ALERT_OFFSET + TLS_ALERT_NO_RENEGOTIATION
+    );
+};
+
 #[derive(Debug)]
 pub struct Error {
     lib: Lib,
     reason: Reason,
     string: Option<String>,
+    location: &'static Location<'static>,
+    verify_code: Option<c_long>,
 }
 
 impl Error {
+    #[track_caller]
     pub fn unexpected_panic() -> Self {
         Self {
             lib: Lib::Ssl,
             reason: Reason::InternalError,
             string: None,
+            location: Location::caller(),
+            verify_code: None,
         }
     }
 
+    #[track_caller]
     pub fn null_pointer() -> Self {
         Self {
             lib: Lib::Ssl,
             reason: Reason::PassedNullParameter,
             string: None,
+            location: Location::caller(),
+            verify_code: None,
         }
     }
 
+    #[track_caller]
     pub fn cannot_lock() -> Self {
         Self {
             lib: Lib::Ssl,
             reason: Reason::UnableToGetWriteLock,
             string: None,
+            location: Location::caller(),
+            verify_code: None,
         }
     }
 
+    #[track_caller]
     pub fn not_supported(hint: &str) -> Self {
         Self {
             lib: Lib::Ssl,
             reason: Reason::Unsupported,
             string: Some(hint.to_string()),
+            location: Location::caller(),
+            verify_code: None,
         }
     }
 
+    #[track_caller]
     pub fn bad_data(hint: &str) -> Self {
         Self {
             lib: Lib::Ssl,
             reason: Reason::OperationFailed,
             string: Some(hint.to_string()),
+            location: Location::caller(),
+            verify_code: None,
         }
     }
 
+    #[track_caller]
     pub fn from_rustls(err: rustls::Error) -> Self {
+        let (lib, reason) = Self::classify_rustls_error(&err);
+        let verify_code = match &err {
+            rustls::Error::InvalidCertificate(cert_err) => Some(Self::verify_code_for(cert_err)),
+            _ => None,
+        };
         Self {
-            lib: Lib::User,
-            reason: Reason::OperationFailed,
+            lib,
+            reason,
             string: Some(err.to_string()),
+            location: Location::caller(),
+            verify_code,
+        }
+    }
+
+    /// The `X509_V_ERR_*` code matching a certificate verification failure,
+    /// for `SSL_get_verify_result` to report. `None` for errors that did not
+    /// arise from certificate verification.
+    pub fn verify_code(&self) -> Option<c_long> {
+        self.verify_code
+    }
+
+    /// Map a rustls `CertificateError` onto the closest `X509_V_ERR_*` code
+    /// (see `openssl/x509_vfy.h`), defaulting to `X509_V_ERR_CERT_REJECTED`
+    /// for variants with no direct match.
+    fn verify_code_for(err: &rustls::CertificateError) -> c_long {
+        use rustls::CertificateError::*;
+
+        match err {
+            Expired => openssl_sys::X509_V_ERR_CERT_HAS_EXPIRED as c_long,
+            NotValidYet => openssl_sys::X509_V_ERR_CERT_NOT_YET_VALID as c_long,
+            UnknownIssuer => openssl_sys::X509_V_ERR_UNABLE_TO_GET_ISSUER_CERT_LOCALLY as c_long,
+            Revoked => openssl_sys::X509_V_ERR_CERT_REVOKED as c_long,
+            BadSignature => openssl_sys::X509_V_ERR_CERT_SIGNATURE_FAILURE as c_long,
+            NotValidForName => openssl_sys::X509_V_ERR_HOSTNAME_MISMATCH as c_long,
+            _ => openssl_sys::X509_V_ERR_CERT_REJECTED as c_long,
+        }
+    }
+
+    /// Map a `rustls::Error` onto the closest standard OpenSSL `(lib, reason)`
+    /// pair, so `ERR_GET_REASON` distinguishes failure causes the way real
+    /// OpenSSL does. Falls back to `(Lib::User, Reason::OperationFailed)`
+    /// for variants with no direct OpenSSL analogue.
+    fn classify_rustls_error(err: &rustls::Error) -> (Lib, Reason) {
+        use rustls::{AlertDescription, Error as E, PeerIncompatible};
+
+        match err {
+            E::InvalidCertificate(_) => (Lib::Ssl, Reason::CertificateVerifyFailed),
+            E::PeerIncompatible(PeerIncompatible::NoCipherSuitesInCommon) => {
+                (Lib::Ssl, Reason::NoSharedCipher)
+            }
+            E::PeerIncompatible(_) => (Lib::Ssl, Reason::UnsupportedProtocol),
+            // Unlike `PeerIncompatible` (we couldn't agree on a protocol or
+            // feature), `PeerMisbehaved` means the peer violated the
+            // protocol outright; don't lump the two together under the
+            // same reason.
+            E::PeerMisbehaved(_) => (Lib::User, Reason::OperationFailed),
+            E::AlertReceived(alert) => match alert {
+                AlertDescription::AccessDenied => (Lib::Ssl, Reason::TlsV1AlertAccessDenied),
+                AlertDescription::UnknownCA => (Lib::Ssl, Reason::TlsV1AlertUnknownCa),
+                AlertDescription::DecodeError => (Lib::Ssl, Reason::TlsV1AlertDecodeError),
+                AlertDescription::DecryptError => (Lib::Ssl, Reason::TlsV1AlertDecryptError),
+                AlertDescription::ExportRestriction => {
+                    (Lib::Ssl, Reason::TlsV1AlertExportRestriction)
+                }
+                AlertDescription::ProtocolVersion => (Lib::Ssl, Reason::TlsV1AlertProtocolVersion),
+                AlertDescription::InsufficientSecurity => {
+                    (Lib::Ssl, Reason::TlsV1AlertInsufficientSecurity)
+                }
+                AlertDescription::InternalError => (Lib::Ssl, Reason::TlsV1AlertInternalError),
+                AlertDescription::UserCanceled => (Lib::Ssl, Reason::TlsV1AlertUserCancelled),
+                AlertDescription::NoRenegotiation => (Lib::Ssl, Reason::TlsV1AlertNoRenegotiation),
+                _ => (Lib::User, Reason::OperationFailed),
+            },
+            _ => (Lib::User, Reason::OperationFailed),
         }
     }
 
+    #[track_caller]
     pub fn from_io(err: std::io::Error) -> Self {
         Self {
             lib: Lib::User,
             reason: Reason::OperationFailed,
             string: Some(err.to_string()),
+            location: Location::caller(),
+            verify_code: None,
         }
     }
 
@@ -101,20 +263,32 @@ impl Error {
                 .unwrap_or_else(|| format!("{:?}", self.reason)),
         )
         .unwrap();
-        // safety: b"%s\0" satisfies requirements of from_bytes_with_nul_unchecked.
-        let fmt = unsafe { CStr::from_bytes_with_nul_unchecked(b"%s\0") };
+        let fmt = c"%s";
+        // safety: source file paths don't contain embedded nuls.
+        let file = CString::new(self.location.file()).unwrap();
+        let func = c"rustls-libssl";
         unsafe {
             ERR_new();
             // nb. miri cannot do variadic functions, so we define a miri-only equivalent
             #[cfg(not(miri))]
-            ERR_set_error(
-                self.lib as c_int,
-                self.reason as c_int,
-                fmt.as_ptr(),
-                cstr.as_ptr(),
-            );
+            {
+                ERR_set_debug(file.as_ptr(), self.location.line() as c_int, func.as_ptr());
+                ERR_set_error(
+                    self.lib as c_int,
+                    self.reason as c_int,
+                    fmt.as_ptr(),
+                    cstr.as_ptr(),
+                );
+            }
             #[cfg(miri)]
-            crate::miri::ERR_set_error(self.lib as c_int, self.reason as c_int, cstr.as_ptr());
+            {
+                crate::miri::ERR_set_debug(
+                    file.as_ptr(),
+                    self.location.line() as c_int,
+                    func.as_ptr(),
+                );
+                crate::miri::ERR_set_error(self.lib as c_int, self.reason as c_int, cstr.as_ptr());
+            }
         }
         self
     }
@@ -176,6 +350,188 @@ impl From<Error> for () {
     }
 }
 
+/// The `SSL_ERROR_*` family a failure should be reported as from
+/// `SSL_get_error` (see `openssl/ssl.h`). Distinct from `Reason`, which is
+/// the `ERR_GET_REASON` detail pushed onto the error stack: several
+/// `ErrorKind`s (eg. `WantRead`) are not really errors at all and must
+/// not be raised there.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// `SSL_ERROR_SSL`: a protocol or internal failure; see the error
+    /// stack (via `raise()`) for detail.
+    Ssl,
+
+    /// `SSL_ERROR_WANT_READ`: retry once more data can be read.
+    WantRead,
+
+    /// `SSL_ERROR_WANT_WRITE`: retry once the transport accepts more data.
+    WantWrite,
+
+    /// `SSL_ERROR_SYSCALL`: the underlying I/O failed with no TLS-level
+    /// explanation.
+    Syscall,
+}
+
+/// Wraps an `Error` for OpenSSL's I/O-shaped entry points (`SSL_read`,
+/// `SSL_write`, `SSL_connect`, `SSL_accept`, `SSL_do_handshake`). These
+/// signal failure by returning a value `<= 0`, leaving the caller to
+/// resolve the cause via `SSL_get_error` instead of the return value
+/// alone, so plain `0` (which OpenSSL reserves for a clean shutdown)
+/// would be actively misleading.
+///
+/// The `SSL` object layer is expected to stash the `ErrorKind` from a
+/// raised `IoError` so its `SSL_get_error` can report it.
+#[derive(Debug)]
+pub struct IoError {
+    error: Error,
+    kind: ErrorKind,
+}
+
+impl IoError {
+    pub fn new(error: Error, kind: ErrorKind) -> Self {
+        Self { error, kind }
+    }
+
+    /// Classifies a `std::io::Error` from the underlying transport,
+    /// treating `WouldBlock` as `WantWrite` or `WantRead` depending on
+    /// which direction was being attempted, and anything else as a
+    /// syscall-level failure.
+    #[track_caller]
+    pub fn from_io(err: std::io::Error, for_write: bool) -> Self {
+        let kind = match (err.kind(), for_write) {
+            (std::io::ErrorKind::WouldBlock, true) => ErrorKind::WantWrite,
+            (std::io::ErrorKind::WouldBlock, false) => ErrorKind::WantRead,
+            _ => ErrorKind::Syscall,
+        };
+        Self::new(Error::from_io(err), kind)
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Add the underlying error to the openssl error stack, if it's
+    /// actually an error (ie. not `WantRead`/`WantWrite`), and return
+    /// self so the caller can still recover `kind()`.
+    pub fn raise(self) -> Self {
+        let error = match self.kind {
+            ErrorKind::WantRead | ErrorKind::WantWrite => self.error,
+            ErrorKind::Ssl | ErrorKind::Syscall => self.error.raise(),
+        };
+        Self { error, ..self }
+    }
+}
+
+impl From<IoError> for c_int {
+    fn from(_: IoError) -> Self {
+        // `SSL_read`/`SSL_write`/`SSL_connect`/`SSL_accept`/
+        // `SSL_do_handshake` all use `-1` here; `0` is reserved for a
+        // clean shutdown. `SSL_get_error` resolves the specific cause
+        // from the `ErrorKind` this carries.
+        -1
+    }
+}
+
+/// A single slot for the most recent `ErrorKind` seen on an `SSL` object's
+/// I/O-shaped entry points. It is updated via `IoError::raise_into` and
+/// read back with `get()`, so an `SSL` object that embeds one can have its
+/// `SSL_get_error` pick the right `SSL_ERROR_*` constant.
+///
+/// Real OpenSSL's `SSL_get_error` isn't a one-shot call: applications are
+/// free to query it more than once after a single failed `SSL_read`/
+/// `SSL_write` (eg. to log the cause, then branch on it). So `get()`
+/// doesn't consume the slot; it's only overwritten by the *next*
+/// `IoError::raise_into` call.
+#[derive(Debug, Default)]
+pub struct LastIoError {
+    kind: Cell<Option<ErrorKind>>,
+}
+
+impl LastIoError {
+    pub fn get(&self) -> Option<ErrorKind> {
+        self.kind.get()
+    }
+}
+
+impl IoError {
+    /// Raise the underlying error (if any) and record `kind()` into
+    /// `slot`, so a subsequent `SSL_get_error` on the same `SSL` object
+    /// can report it.
+    pub fn raise_into(self, slot: &LastIoError) -> Self {
+        let this = self.raise();
+        slot.kind.set(Some(this.kind));
+        this
+    }
+}
+
+/// The reason strings this crate raises under `Lib::Ssl`/`Lib::User` that
+/// aren't already known to OpenSSL (unlike the standard `SSL_R_*` codes in
+/// `Reason`, which OpenSSL's own string tables already describe).
+const REASON_STRINGS: &[(Lib, Reason, &[u8])] = &[
+    (
+        Lib::Ssl,
+        Reason::PassedNullParameter,
+        b"passed a null parameter\0",
+    ),
+    (Lib::Ssl, Reason::InternalError, b"internal error\0"),
+    (
+        Lib::Ssl,
+        Reason::UnableToGetWriteLock,
+        b"unable to get write lock\0",
+    ),
+    (Lib::Ssl, Reason::OperationFailed, b"operation failed\0"),
+    (Lib::User, Reason::OperationFailed, b"operation failed\0"),
+    (Lib::Ssl, Reason::Unsupported, b"unsupported\0"),
+];
+
+// `ERR_LIB_OFFSET`/`ERR_REASON_MASK` from `openssl/err.h`: `ERR_PACK(lib, 0,
+// reason)` is `(lib & 0xFF) << ERR_LIB_OFFSET | (reason & ERR_REASON_MASK)`.
+const ERR_LIB_OFFSET: u32 = 23;
+const ERR_REASON_MASK: u32 = 0x7FFFFF;
+
+/// Packs `(lib, reason)` the way `ERR_STRING_DATA::error` expects; mirrors
+/// the `ERR_PACK` macro in `openssl/err.h` (the function component is
+/// unused by lookup, so it's left as zero).
+const fn err_pack(lib: Lib, reason: Reason) -> c_ulong {
+    (((lib as i32 as u32) << ERR_LIB_OFFSET) | (reason as i32 as u32 & ERR_REASON_MASK)) as c_ulong
+}
+
+/// Registers human-readable text for this crate's custom `Reason` codes,
+/// so `ERR_error_string`/`ERR_reason_error_string` produce real text
+/// instead of a bare `reason(263)`. Idempotent; called once from
+/// `OPENSSL_init_ssl`.
+pub(crate) fn init_reason_strings() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        // Leaked deliberately: `ERR_load_strings` keeps a pointer to this
+        // table for the life of the process, same as OpenSSL's own
+        // generated string tables.
+        let mut entries: Vec<ERR_STRING_DATA> = REASON_STRINGS
+            .iter()
+            .map(|(lib, reason, text)| ERR_STRING_DATA {
+                error: err_pack(*lib, *reason),
+                // safety: every entry above is a static byte string literal
+                // ending in `\0` with no interior nuls.
+                string: unsafe { CStr::from_bytes_with_nul_unchecked(text) }.as_ptr(),
+            })
+            .collect();
+        entries.push(ERR_STRING_DATA {
+            error: 0,
+            string: ptr::null(),
+        });
+        let entries: &'static [ERR_STRING_DATA] = Vec::leak(entries);
+
+        // `ERR_load_strings` ORs `ERR_PACK(lib, 0, 0)` into every entry;
+        // each entry already carries its own lib (see `err_pack`), so pass 0.
+        unsafe {
+            #[cfg(not(miri))]
+            ERR_load_strings(0, entries.as_ptr() as *mut ERR_STRING_DATA);
+            #[cfg(miri)]
+            crate::miri::ERR_load_strings(0, entries.as_ptr() as *mut ERR_STRING_DATA);
+        }
+    });
+}
+
 #[macro_export]
 macro_rules! ffi_panic_boundary {
     ( $($tt:tt)* ) => {
@@ -192,3 +548,146 @@ macro_rules! ffi_panic_boundary {
 }
 
 pub(crate) use ffi_panic_boundary;
+
+#[cfg(all(test, not(miri)))]
+mod tests {
+    use super::*;
+
+    // Miri can't make the real FFI calls this test drives, so this whole
+    // module is compiled out there (`not(miri)` above); no test exercises
+    // `init_reason_strings`'s `#[cfg(miri)]` branch.
+    #[test]
+    fn reason_strings_round_trip() {
+        init_reason_strings();
+
+        for (lib, reason, expected) in REASON_STRINGS {
+            let err = Error {
+                lib: *lib,
+                reason: *reason,
+                string: None,
+                location: Location::caller(),
+                verify_code: None,
+            };
+            err.raise();
+
+            let code = unsafe { openssl_sys::ERR_get_error() };
+            let got = unsafe { CStr::from_ptr(openssl_sys::ERR_reason_error_string(code)) };
+            assert_eq!(got.to_bytes_with_nul(), *expected, "reason {reason:?}");
+
+            unsafe { openssl_sys::ERR_clear_error() };
+        }
+    }
+
+    #[test]
+    fn io_error_from_io_classification() {
+        let cases = [
+            (std::io::ErrorKind::WouldBlock, false, ErrorKind::WantRead),
+            (std::io::ErrorKind::WouldBlock, true, ErrorKind::WantWrite),
+            (std::io::ErrorKind::Other, false, ErrorKind::Syscall),
+            (std::io::ErrorKind::Other, true, ErrorKind::Syscall),
+        ];
+
+        for (io_kind, for_write, expected) in cases {
+            let io_err = IoError::from_io(std::io::Error::from(io_kind), for_write);
+            assert_eq!(io_err.kind(), expected, "{io_kind:?}, for_write={for_write}");
+        }
+    }
+
+    #[test]
+    fn io_error_raise_only_pushes_real_errors() {
+        for kind in [ErrorKind::WantRead, ErrorKind::WantWrite] {
+            unsafe { openssl_sys::ERR_clear_error() };
+            IoError::new(Error::from_io(std::io::Error::from(std::io::ErrorKind::Other)), kind)
+                .raise();
+            assert_eq!(
+                unsafe { openssl_sys::ERR_get_error() },
+                0,
+                "{kind:?} must not be raised to the error stack"
+            );
+        }
+
+        for kind in [ErrorKind::Syscall, ErrorKind::Ssl] {
+            unsafe { openssl_sys::ERR_clear_error() };
+            IoError::new(Error::from_io(std::io::Error::from(std::io::ErrorKind::Other)), kind)
+                .raise();
+            assert_ne!(
+                unsafe { openssl_sys::ERR_get_error() },
+                0,
+                "{kind:?} must be raised to the error stack"
+            );
+            unsafe { openssl_sys::ERR_clear_error() };
+        }
+    }
+
+    #[test]
+    fn classify_rustls_error_mapping() {
+        use rustls::{AlertDescription, CertificateError, Error as E, PeerIncompatible};
+
+        let cases = [
+            (
+                E::InvalidCertificate(CertificateError::Expired),
+                (Lib::Ssl, Reason::CertificateVerifyFailed),
+            ),
+            (
+                E::PeerIncompatible(PeerIncompatible::NoCipherSuitesInCommon),
+                (Lib::Ssl, Reason::NoSharedCipher),
+            ),
+            (
+                E::PeerIncompatible(PeerIncompatible::NoSignatureSchemesInCommon),
+                (Lib::Ssl, Reason::UnsupportedProtocol),
+            ),
+            (
+                E::PeerMisbehaved(rustls::PeerMisbehaved::TooManyWarningAlertsReceived),
+                (Lib::User, Reason::OperationFailed),
+            ),
+            (
+                E::AlertReceived(AlertDescription::AccessDenied),
+                (Lib::Ssl, Reason::TlsV1AlertAccessDenied),
+            ),
+            (
+                E::AlertReceived(AlertDescription::UnknownCA),
+                (Lib::Ssl, Reason::TlsV1AlertUnknownCa),
+            ),
+            (
+                E::AlertReceived(AlertDescription::CloseNotify),
+                (Lib::User, Reason::OperationFailed),
+            ),
+            (E::DecryptError, (Lib::User, Reason::OperationFailed)),
+        ];
+
+        for (err, (expected_lib, expected_reason)) in cases {
+            let (lib, reason) = Error::classify_rustls_error(&err);
+            assert_eq!(
+                (lib as i32, reason as i32),
+                (expected_lib as i32, expected_reason as i32),
+                "{err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn verify_code_for_mapping() {
+        use rustls::CertificateError::*;
+
+        let cases = [
+            (Expired, openssl_sys::X509_V_ERR_CERT_HAS_EXPIRED),
+            (NotValidYet, openssl_sys::X509_V_ERR_CERT_NOT_YET_VALID),
+            (
+                UnknownIssuer,
+                openssl_sys::X509_V_ERR_UNABLE_TO_GET_ISSUER_CERT_LOCALLY,
+            ),
+            (Revoked, openssl_sys::X509_V_ERR_CERT_REVOKED),
+            (BadSignature, openssl_sys::X509_V_ERR_CERT_SIGNATURE_FAILURE),
+            (NotValidForName, openssl_sys::X509_V_ERR_HOSTNAME_MISMATCH),
+            (BadEncoding, openssl_sys::X509_V_ERR_CERT_REJECTED),
+        ];
+
+        for (err, expected) in cases {
+            assert_eq!(
+                Error::verify_code_for(&err),
+                expected as c_long,
+                "{err:?}"
+            );
+        }
+    }
+}
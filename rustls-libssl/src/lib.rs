@@ -0,0 +1,19 @@
+mod error;
+
+#[cfg(miri)]
+mod miri;
+
+pub use error::Error;
+
+/// OpenSSL's `OPENSSL_init_ssl` ABI entry point. Real callers invoke this
+/// (directly, or via the legacy `SSL_library_init`) before using anything
+/// else in the library; this is where one-time setup like registering
+/// this crate's error strings belongs.
+#[no_mangle]
+pub extern "C" fn OPENSSL_init_ssl(
+    _opts: u64,
+    _settings: *const core::ffi::c_void,
+) -> core::ffi::c_int {
+    error::init_reason_strings();
+    1
+}
@@ -0,0 +1,37 @@
+//! Stand-ins for `openssl-sys` FFI entry points that `error.rs` calls
+//! under miri: miri can't cross the FFI boundary into the real OpenSSL at
+//! all, and separately can't do variadic calls like the real
+//! `ERR_set_error`. They check that their string arguments are readable C
+//! strings and record the numeric ones in thread-locals that nothing reads
+//! back. `ERR_new` has no stand-in here; `error.rs` calls it directly.
+
+use core::ffi::{c_char, c_int};
+use std::cell::Cell;
+use std::ffi::CStr;
+
+use openssl_sys::ERR_STRING_DATA;
+
+thread_local! {
+    static LAST_ERROR: Cell<Option<(c_int, c_int)>> = const { Cell::new(None) };
+    static LAST_DEBUG: Cell<Option<(c_int,)>> = const { Cell::new(None) };
+}
+
+/// Miri-only equivalent of `ERR_set_error`, without the variadic `fmt`/
+/// `...` arguments: the caller has already rendered `data` down to a
+/// plain C string.
+pub unsafe fn ERR_set_error(lib: c_int, reason: c_int, data: *const c_char) {
+    let _ = unsafe { CStr::from_ptr(data) };
+    LAST_ERROR.set(Some((lib, reason)));
+}
+
+/// Miri-only equivalent of `ERR_set_debug`.
+pub unsafe fn ERR_set_debug(file: *const c_char, line: c_int, func: *const c_char) {
+    let _ = unsafe { CStr::from_ptr(file) };
+    let _ = unsafe { CStr::from_ptr(func) };
+    LAST_DEBUG.set(Some((line,)));
+}
+
+/// Miri-only equivalent of `ERR_load_strings`.
+pub unsafe fn ERR_load_strings(_lib: c_int, _str: *mut ERR_STRING_DATA) -> c_int {
+    1
+}